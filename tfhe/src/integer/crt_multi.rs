@@ -0,0 +1,607 @@
+//! Module with the definition of a hybrid CRT representation where each residue is itself a
+//! multi-block radix, rather than a single shortint block.
+//!
+//! The classical CRT layer (see [`crate::integer::ciphertext::CrtCiphertext`]) is limited to tiny
+//! coprime moduli, since each residue must fit in a single shortint block. [`CrtMultiCiphertext`]
+//! lifts this restriction by decomposing each residue into a [`RadixClientKey`]-sized radix,
+//! keeping the independent-lane parallelism CRT already provides while scaling to much larger
+//! integer domains.
+
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::server_key::CheckError;
+use crate::integer::server_key::CheckError::CarryFull;
+use crate::integer::{ClientKey, RadixClientKey, ServerKey};
+use crate::shortint::parameters::Parameters;
+use rayon::prelude::*;
+
+/// A CRT ciphertext whose residues are themselves multi-block radixes.
+///
+/// `blocks[i]` holds the residue modulo `moduli[i]`, represented as a [`RadixCiphertext`] large
+/// enough to span that modulus.
+#[derive(Clone)]
+pub struct CrtMultiCiphertext {
+    pub(crate) blocks: Vec<RadixCiphertext>,
+    pub(crate) moduli: Vec<u64>,
+}
+
+/// The client key counterpart of [`CrtMultiCiphertext`].
+///
+/// It holds one [`RadixClientKey`] per residue, so that a basis of larger coprime moduli can each
+/// be decomposed into the number of blocks their size requires.
+pub struct CrtMultiClientKey {
+    keys: Vec<RadixClientKey>,
+    moduli: Vec<u64>,
+}
+
+impl CrtMultiClientKey {
+    /// Creates a new [`CrtMultiClientKey`] for the given `basis`, generating `num_blocks[i]`
+    /// blocks of `parameters_set[i]` for the `i`-th residue.
+    ///
+    /// Taking one [`Parameters`] per residue (rather than a single one shared across `basis`) is
+    /// what lets a small modulus such as `2` use far less precision than a larger one such as
+    /// `17`, instead of forcing every residue up to the same parameter set.
+    pub fn new(parameters_set: &[Parameters], basis: Vec<u64>, num_blocks: &[usize]) -> Self {
+        assert_eq!(parameters_set.len(), basis.len());
+        assert_eq!(basis.len(), num_blocks.len());
+
+        for ((params, modulus), blocks) in parameters_set
+            .iter()
+            .zip(basis.iter())
+            .zip(num_blocks.iter())
+        {
+            let capacity = (params.message_modulus.0 as u64).pow(*blocks as u32);
+            assert!(
+                capacity >= *modulus,
+                "{} blocks of message_modulus {} can only represent up to {}, which is not \
+                 enough to hold the residue modulo {}",
+                blocks,
+                params.message_modulus.0,
+                capacity,
+                modulus
+            );
+        }
+
+        let keys = parameters_set
+            .iter()
+            .zip(num_blocks.iter())
+            .map(|(params, blocks)| {
+                let cks = ClientKey::new(*params);
+                RadixClientKey::from((cks, *blocks))
+            })
+            .collect();
+
+        Self { keys, moduli: basis }
+    }
+
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// Encrypts `message` into a [`CrtMultiCiphertext`], splitting it into one residue per
+    /// modulus in [`Self::moduli`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_crt_multi;
+    /// use tfhe::shortint::parameters::{PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2};
+    ///
+    /// let parameters_set = vec![PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2];
+    /// let basis = vec![2, 17];
+    /// let num_blocks = vec![1, 3];
+    /// let (cks, _sks) = gen_keys_crt_multi(&parameters_set, basis, &num_blocks);
+    ///
+    /// let msg = 11;
+    /// let ct = cks.encrypt(msg);
+    /// let dec = cks.decrypt(&ct);
+    /// assert_eq!(msg, dec);
+    /// ```
+    pub fn encrypt(&self, message: u64) -> CrtMultiCiphertext {
+        let blocks = self
+            .keys
+            .iter()
+            .zip(self.moduli.iter())
+            .map(|(key, modulus)| key.encrypt(message % modulus))
+            .collect();
+
+        CrtMultiCiphertext {
+            blocks,
+            moduli: self.moduli.clone(),
+        }
+    }
+
+    /// Decrypts a [`CrtMultiCiphertext`] back to its cleartext value, reconstructing it from its
+    /// per-residue decryptions via CRT.
+    pub fn decrypt(&self, ct: &CrtMultiCiphertext) -> u64 {
+        let residues: Vec<u64> = self
+            .keys
+            .iter()
+            .zip(ct.blocks.iter())
+            .map(|(key, block)| key.decrypt(block))
+            .collect();
+
+        crt_reconstruct(&residues, &ct.moduli)
+    }
+}
+
+/// Reconstructs the unique value modulo `moduli.iter().product()` whose residue modulo
+/// `moduli[i]` is `residues[i]`, via iterative pairwise CRT combination (`moduli` must be
+/// pairwise coprime).
+fn crt_reconstruct(residues: &[u64], moduli: &[u64]) -> u64 {
+    let mut acc_modulus = moduli[0];
+    let mut acc_value = residues[0] % acc_modulus;
+
+    for (&modulus, &residue) in moduli.iter().zip(residues.iter()).skip(1) {
+        let inverse = mod_inverse(acc_modulus % modulus, modulus);
+        let diff = (residue + modulus - acc_value % modulus) % modulus;
+        let k = (diff as u128 * inverse as u128 % modulus as u128) as u64;
+
+        acc_value += acc_modulus * k;
+        acc_modulus *= modulus;
+    }
+
+    acc_value
+}
+
+/// Computes the inverse of `a` modulo `modulus` via the extended Euclidean algorithm.
+fn mod_inverse(a: u64, modulus: u64) -> u64 {
+    let (mut old_r, mut r) = (a as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        let next_r = old_r - quotient * r;
+        old_r = r;
+        r = next_r;
+        let next_s = old_s - quotient * s;
+        old_s = s;
+        s = next_s;
+    }
+
+    ((old_s % modulus as i128 + modulus as i128) % modulus as i128) as u64
+}
+
+/// The server key counterpart of [`CrtMultiCiphertext`].
+///
+/// It holds one [`ServerKey`] per residue (derived from the matching [`RadixClientKey`] in a
+/// [`CrtMultiClientKey`]), so each lane keeps its own precision independently of the others.
+pub struct CrtMultiServerKey {
+    keys: Vec<ServerKey>,
+    moduli: Vec<u64>,
+}
+
+impl CrtMultiServerKey {
+    /// Generates a [`CrtMultiServerKey`] matching a [`CrtMultiClientKey`].
+    pub fn new(cks: &CrtMultiClientKey) -> Self {
+        let keys = cks.keys.iter().map(ServerKey::new).collect();
+
+        Self {
+            keys,
+            moduli: cks.moduli.clone(),
+        }
+    }
+
+    /// Computes homomorphically an addition between a scalar and a ciphertext, residue-wise, with
+    /// internal carry propagation on each lane.
+    pub fn unchecked_crt_multi_scalar_add_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        let blocks = self
+            .keys
+            .par_iter()
+            .zip(ct.blocks.par_iter())
+            .zip(ct.moduli.par_iter())
+            .map(|((key, block), modulus)| {
+                let scalar_i = scalar % modulus;
+                let mut result = key.unchecked_scalar_add(block, scalar_i);
+                key.full_propagate(&mut result);
+                result
+            })
+            .collect();
+
+        CrtMultiCiphertext {
+            blocks,
+            moduli: ct.moduli.clone(),
+        }
+    }
+
+    /// Computes homomorphically a subtraction of a scalar from a ciphertext, residue-wise.
+    ///
+    /// Each lane reduces the subtraction to adding `(mod_i - (scalar % mod_i)) % mod_i`, so the
+    /// same carry-propagating add path used by [`Self::unchecked_crt_multi_scalar_add_parallelized`]
+    /// handles the borrow.
+    pub fn unchecked_crt_multi_scalar_sub_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        let blocks = self
+            .keys
+            .par_iter()
+            .zip(ct.blocks.par_iter())
+            .zip(ct.moduli.par_iter())
+            .map(|((key, block), modulus)| {
+                let neg_scalar_i = (modulus - (scalar % modulus)) % modulus;
+                let mut result = key.unchecked_scalar_add(block, neg_scalar_i);
+                key.full_propagate(&mut result);
+                result
+            })
+            .collect();
+
+        CrtMultiCiphertext {
+            blocks,
+            moduli: ct.moduli.clone(),
+        }
+    }
+
+    /// Computes homomorphically a multiplication of a ciphertext by a scalar, residue-wise.
+    pub fn unchecked_crt_multi_scalar_mul_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        let blocks = self
+            .keys
+            .par_iter()
+            .zip(ct.blocks.par_iter())
+            .zip(ct.moduli.par_iter())
+            .map(|((key, block), modulus)| {
+                let scalar_i = scalar % modulus;
+                let mut result = key.unchecked_scalar_mul(block, scalar_i);
+                key.full_propagate(&mut result);
+                result
+            })
+            .collect();
+
+        CrtMultiCiphertext {
+            blocks,
+            moduli: ct.moduli.clone(),
+        }
+    }
+
+    /// Verifies if a scalar addition can be computed on every residue lane without exceeding its
+    /// ciphertext's capacity.
+    pub fn is_crt_multi_scalar_add_possible(&self, ct: &CrtMultiCiphertext, scalar: u64) -> bool {
+        self.keys
+            .iter()
+            .zip(ct.blocks.iter())
+            .zip(ct.moduli.iter())
+            .all(|((key, block), modulus)| {
+                let scalar_i = scalar % modulus;
+                key.is_scalar_add_possible(block, scalar_i)
+            })
+    }
+
+    /// Computes homomorphically an addition between a scalar and a ciphertext, residue-wise.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext. Otherwise
+    /// [CheckError::CarryFull] is returned.
+    pub fn checked_crt_multi_scalar_add_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<CrtMultiCiphertext, CheckError> {
+        if self.is_crt_multi_scalar_add_possible(ct, scalar) {
+            Ok(self.unchecked_crt_multi_scalar_add_parallelized(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically an addition between a scalar and a ciphertext, residue-wise.
+    ///
+    /// If the operation can be performed, the result is assigned to `ct`. Otherwise
+    /// [CheckError::CarryFull] is returned, and `ct` is not modified.
+    pub fn checked_crt_multi_scalar_add_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<(), CheckError> {
+        if self.is_crt_multi_scalar_add_possible(ct, scalar) {
+            *ct = self.unchecked_crt_multi_scalar_add_parallelized(ct, scalar);
+            Ok(())
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically the addition of a ciphertext with a scalar, residue-wise.
+    ///
+    /// Every lane that cannot accept the addition directly is first fully propagated.
+    pub fn smart_crt_multi_scalar_add_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        if !self.is_crt_multi_scalar_add_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        self.unchecked_crt_multi_scalar_add_parallelized(ct, scalar)
+    }
+
+    /// Computes homomorphically the addition of a ciphertext with a scalar, residue-wise.
+    ///
+    /// Every lane that cannot accept the addition directly is first fully propagated. The result
+    /// is assigned to `ct`.
+    pub fn smart_crt_multi_scalar_add_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) {
+        if !self.is_crt_multi_scalar_add_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        *ct = self.unchecked_crt_multi_scalar_add_parallelized(ct, scalar);
+    }
+
+    /// Verifies if a scalar subtraction can be computed on every residue lane without exceeding
+    /// its ciphertext's capacity.
+    pub fn is_crt_multi_scalar_sub_possible(&self, ct: &CrtMultiCiphertext, scalar: u64) -> bool {
+        self.keys
+            .iter()
+            .zip(ct.blocks.iter())
+            .zip(ct.moduli.iter())
+            .all(|((key, block), modulus)| {
+                let neg_scalar_i = (modulus - (scalar % modulus)) % modulus;
+                key.is_scalar_add_possible(block, neg_scalar_i)
+            })
+    }
+
+    /// Computes homomorphically a subtraction of a scalar from a ciphertext, residue-wise.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext. Otherwise
+    /// [CheckError::CarryFull] is returned.
+    pub fn checked_crt_multi_scalar_sub_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<CrtMultiCiphertext, CheckError> {
+        if self.is_crt_multi_scalar_sub_possible(ct, scalar) {
+            Ok(self.unchecked_crt_multi_scalar_sub_parallelized(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a subtraction of a scalar from a ciphertext, residue-wise.
+    ///
+    /// If the operation can be performed, the result is assigned to `ct`. Otherwise
+    /// [CheckError::CarryFull] is returned, and `ct` is not modified.
+    pub fn checked_crt_multi_scalar_sub_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<(), CheckError> {
+        if self.is_crt_multi_scalar_sub_possible(ct, scalar) {
+            *ct = self.unchecked_crt_multi_scalar_sub_parallelized(ct, scalar);
+            Ok(())
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically the subtraction of a scalar from a ciphertext, residue-wise.
+    ///
+    /// Every lane that cannot accept the subtraction directly is first fully propagated.
+    pub fn smart_crt_multi_scalar_sub_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        if !self.is_crt_multi_scalar_sub_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        self.unchecked_crt_multi_scalar_sub_parallelized(ct, scalar)
+    }
+
+    /// Computes homomorphically the subtraction of a scalar from a ciphertext, residue-wise.
+    ///
+    /// Every lane that cannot accept the subtraction directly is first fully propagated. The
+    /// result is assigned to `ct`.
+    pub fn smart_crt_multi_scalar_sub_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) {
+        if !self.is_crt_multi_scalar_sub_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        *ct = self.unchecked_crt_multi_scalar_sub_parallelized(ct, scalar);
+    }
+
+    /// Verifies if a scalar multiplication can be computed on every residue lane without
+    /// exceeding its ciphertext's capacity.
+    pub fn is_crt_multi_scalar_mul_possible(&self, ct: &CrtMultiCiphertext, scalar: u64) -> bool {
+        self.keys
+            .iter()
+            .zip(ct.blocks.iter())
+            .zip(ct.moduli.iter())
+            .all(|((key, block), modulus)| {
+                let scalar_i = scalar % modulus;
+                key.is_scalar_mul_possible(block, scalar_i)
+            })
+    }
+
+    /// Computes homomorphically a multiplication of a ciphertext by a scalar, residue-wise.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext. Otherwise
+    /// [CheckError::CarryFull] is returned.
+    pub fn checked_crt_multi_scalar_mul_parallelized(
+        &self,
+        ct: &CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<CrtMultiCiphertext, CheckError> {
+        if self.is_crt_multi_scalar_mul_possible(ct, scalar) {
+            Ok(self.unchecked_crt_multi_scalar_mul_parallelized(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a multiplication of a ciphertext by a scalar, residue-wise.
+    ///
+    /// If the operation can be performed, the result is assigned to `ct`. Otherwise
+    /// [CheckError::CarryFull] is returned, and `ct` is not modified.
+    pub fn checked_crt_multi_scalar_mul_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> Result<(), CheckError> {
+        if self.is_crt_multi_scalar_mul_possible(ct, scalar) {
+            *ct = self.unchecked_crt_multi_scalar_mul_parallelized(ct, scalar);
+            Ok(())
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically the multiplication of a ciphertext by a scalar, residue-wise.
+    ///
+    /// Every lane that cannot accept the multiplication directly is first fully propagated.
+    pub fn smart_crt_multi_scalar_mul_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) -> CrtMultiCiphertext {
+        if !self.is_crt_multi_scalar_mul_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        self.unchecked_crt_multi_scalar_mul_parallelized(ct, scalar)
+    }
+
+    /// Computes homomorphically the multiplication of a ciphertext by a scalar, residue-wise.
+    ///
+    /// Every lane that cannot accept the multiplication directly is first fully propagated. The
+    /// result is assigned to `ct`.
+    pub fn smart_crt_multi_scalar_mul_assign_parallelized(
+        &self,
+        ct: &mut CrtMultiCiphertext,
+        scalar: u64,
+    ) {
+        if !self.is_crt_multi_scalar_mul_possible(ct, scalar) {
+            self.full_propagate_crt_multi(ct);
+        }
+
+        *ct = self.unchecked_crt_multi_scalar_mul_parallelized(ct, scalar);
+    }
+
+    /// Fully propagates every residue lane's carries in parallel, so that a subsequent
+    /// `is_*_possible` check on that lane is guaranteed to succeed.
+    fn full_propagate_crt_multi(&self, ct: &mut CrtMultiCiphertext) {
+        self.keys
+            .par_iter()
+            .zip(ct.blocks.par_iter_mut())
+            .for_each(|(key, block)| key.full_propagate(block));
+    }
+
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integer::gen_keys_crt_multi;
+    use crate::shortint::parameters::{PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2};
+
+    fn keys() -> (CrtMultiClientKey, CrtMultiServerKey) {
+        let parameters_set = vec![PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2];
+        let basis = vec![2, 17];
+        let num_blocks = vec![1, 3];
+        gen_keys_crt_multi(&parameters_set, basis, &num_blocks)
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let (cks, _sks) = keys();
+
+        for msg in [0u64, 1, 11, 33] {
+            let ct = cks.encrypt(msg);
+            assert_eq!(msg % 34, cks.decrypt(&ct));
+        }
+    }
+
+    #[test]
+    fn test_crt_reconstruct() {
+        let moduli = [2, 17];
+        for value in [0u64, 1, 11, 33] {
+            let residues: Vec<u64> = moduli.iter().map(|m| value % m).collect();
+            assert_eq!(value % 34, crt_reconstruct(&residues, &moduli));
+        }
+    }
+
+    #[test]
+    fn test_mod_inverse() {
+        for (a, modulus) in [(3u64, 17u64), (5, 13), (1, 7)] {
+            let inverse = mod_inverse(a, modulus);
+            assert_eq!((a * inverse) % modulus, 1);
+        }
+    }
+
+    #[test]
+    fn test_unchecked_scalar_ops() {
+        let (cks, sks) = keys();
+
+        let msg = 11;
+        let scalar = 5;
+        let ct = cks.encrypt(msg);
+
+        let add_res = sks.unchecked_crt_multi_scalar_add_parallelized(&ct, scalar);
+        assert_eq!((msg + scalar) % 34, cks.decrypt(&add_res));
+
+        let sub_res = sks.unchecked_crt_multi_scalar_sub_parallelized(&ct, scalar);
+        assert_eq!((msg + 34 - scalar) % 34, cks.decrypt(&sub_res));
+
+        let mul_res = sks.unchecked_crt_multi_scalar_mul_parallelized(&ct, scalar);
+        assert_eq!((msg * scalar) % 34, cks.decrypt(&mul_res));
+    }
+
+    #[test]
+    fn test_checked_and_smart_scalar_add() {
+        let (cks, sks) = keys();
+
+        let msg = 11;
+        let scalar = 5;
+        let mut ct = cks.encrypt(msg);
+
+        let ct_res = sks.checked_crt_multi_scalar_add_parallelized(&ct, scalar).unwrap();
+        assert_eq!((msg + scalar) % 34, cks.decrypt(&ct_res));
+
+        let ct_res = sks.smart_crt_multi_scalar_add_parallelized(&mut ct, scalar);
+        assert_eq!((msg + scalar) % 34, cks.decrypt(&ct_res));
+    }
+
+    #[test]
+    fn test_heterogeneous_parameters_repeated_ops() {
+        // keys() pairs a 1-bit-message residue (modulus 2) with a 2-bit-message residue (modulus
+        // 17), each under its own Parameters. Running several rounds of parallel per-residue ops
+        // back to back exercises the per-parameter scratch buffer cache keyed by GenKeyId in
+        // ShortintEngine, which must not have one residue's buffers clobber the other's.
+        let (cks, sks) = keys();
+
+        for round in 0..5u64 {
+            let msg = (round * 7) % 34;
+            let ct = cks.encrypt(msg);
+            let ct_res = sks.unchecked_crt_multi_scalar_add_parallelized(&ct, round);
+            assert_eq!((msg + round) % 34, cks.decrypt(&ct_res));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "is not enough to hold the residue")]
+    fn test_new_rejects_insufficient_capacity() {
+        // A single message_modulus=2 block can only hold 0/1, not a residue modulo 17.
+        let parameters_set = vec![PARAM_MESSAGE_1_CARRY_1];
+        let basis = vec![17];
+        let num_blocks = vec![1];
+        CrtMultiClientKey::new(&parameters_set, basis, &num_blocks);
+    }
+}
@@ -53,14 +53,18 @@ mod tests;
 
 pub mod ciphertext;
 pub mod client_key;
+pub mod crt_multi;
 #[cfg(any(test, feature = "internal-keycache"))]
 pub mod keycache;
 pub mod parameters;
+pub mod seeded;
 pub mod server_key;
 pub mod wopbs;
 
 pub use ciphertext::{CrtCiphertext, IntegerCiphertext, RadixCiphertext};
 pub use client_key::{ClientKey, CrtClientKey, RadixClientKey};
+pub use crt_multi::{CrtMultiCiphertext, CrtMultiClientKey, CrtMultiServerKey};
+pub use seeded::{gen_keys_radix_seeded, SeededRadixCiphertext, SeededServerKey};
 pub use server_key::{CheckError, ServerKey};
 
 /// Generate a couple of client and server keys with given parameters
@@ -133,3 +137,33 @@ pub fn gen_keys_crt(
 
     (CrtClientKey::from((cks, basis)), sks)
 }
+
+/// Generate a [CrtMultiClientKey]/[CrtMultiServerKey] pair for a heterogeneous CRT basis, where
+/// [`CrtMultiClientKey::new`] already lets each residue use its own
+/// [`Parameters`](crate::shortint::parameters::Parameters) and its own number of radix blocks.
+///
+/// This is a thin convenience wrapper pairing that client key with its matching server key, the
+/// same way [gen_keys_crt] pairs a [CrtClientKey] with a plain [ServerKey]. Each per-residue
+/// [ServerKey] keeps its own scratch buffers in the thread-local
+/// [`crate::shortint::engine::ShortintEngine`], keyed by parameter configuration, so mixing
+/// several `Parameters` in the same basis does not have one residue's key evict another's.
+///
+/// ```rust
+/// use tfhe::integer::gen_keys_crt_multi;
+/// use tfhe::shortint::parameters::{PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2};
+///
+/// let basis = vec![2, 17];
+/// let parameters_set = vec![PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2];
+/// let num_blocks = vec![1, 3];
+/// let (cks, sks) = gen_keys_crt_multi(&parameters_set, basis, &num_blocks);
+/// ```
+pub fn gen_keys_crt_multi(
+    parameters_set: &[crate::shortint::parameters::Parameters],
+    basis: Vec<u64>,
+    num_blocks: &[usize],
+) -> (CrtMultiClientKey, CrtMultiServerKey) {
+    let cks = CrtMultiClientKey::new(parameters_set, basis, num_blocks);
+    let sks = CrtMultiServerKey::new(&cks);
+
+    (cks, sks)
+}
@@ -0,0 +1,146 @@
+//! Seeded (compressed) keys and ciphertexts for the integer layer.
+//!
+//! Generating and encrypting the usual way produces fully-expanded masks for every block, which
+//! dominate the bytes a client has to upload. A [`SeededRadixCiphertext`]/[`SeededServerKey`]
+//! instead stores only the 128-bit seed used to derive those masks plus the (small) bodies,
+//! shrinking transmission size by roughly half; [`SeededServerKey::decompress`] and
+//! [`SeededRadixCiphertext::decompress`] regenerate the masks deterministically from the seed.
+
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::{ClientKey, RadixClientKey, ServerKey};
+use crate::shortint::ciphertext::CompressedCiphertext;
+use crate::shortint::parameters::Parameters;
+use crate::shortint::SeededServerKey as ShortintSeededServerKey;
+
+/// A [`RadixCiphertext`] whose blocks are stored in their compressed (seeded) form.
+///
+/// Each block only carries the PRNG seed and the ciphertext body; the mask is regenerated on
+/// [`Self::decompress`].
+#[derive(Clone)]
+pub struct SeededRadixCiphertext {
+    blocks: Vec<CompressedCiphertext>,
+}
+
+impl SeededRadixCiphertext {
+    /// Expands every block's mask from its seed, producing a regular [`RadixCiphertext`].
+    pub fn decompress(&self) -> RadixCiphertext {
+        let blocks = self.blocks.iter().map(CompressedCiphertext::decompress).collect();
+
+        RadixCiphertext { blocks }
+    }
+}
+
+impl RadixClientKey {
+    /// Encrypts `message` directly into its compressed (seeded) form, skipping the full mask
+    /// generation a regular [`Self::encrypt`] would do.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    /// let (cks, _sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 27;
+    /// let seeded_ct = cks.encrypt_compressed(msg);
+    /// let ct = seeded_ct.decompress();
+    /// let dec: u64 = cks.decrypt(&ct);
+    /// assert_eq!(msg, dec);
+    /// ```
+    pub fn encrypt_compressed(&self, message: u64) -> SeededRadixCiphertext {
+        let num_blocks = self.num_blocks();
+        let message_modulus = self.parameters().message_modulus.0 as u64;
+
+        let blocks = (0..num_blocks)
+            .map(|i| {
+                let block_value = (message / message_modulus.pow(i as u32)) % message_modulus;
+                self.as_ref().encrypt_compressed(block_value)
+            })
+            .collect();
+
+        SeededRadixCiphertext { blocks }
+    }
+}
+
+/// The compressed counterpart of [`ServerKey`], storing only the seeds needed to regenerate the
+/// bootstrapping and key-switching key masks.
+pub struct SeededServerKey {
+    key: ShortintSeededServerKey,
+}
+
+impl SeededServerKey {
+    /// Generates a [`SeededServerKey`] from a [`ClientKey`], through the engine's own seeder so
+    /// the masks can be regenerated deterministically on [`Self::decompress`].
+    pub fn new(cks: &ClientKey) -> Self {
+        Self {
+            key: ShortintSeededServerKey::new(cks.as_ref()),
+        }
+    }
+
+    /// Expands the bootstrapping and key-switching key masks from their seeds, producing a
+    /// regular [`ServerKey`] usable for homomorphic computation.
+    pub fn decompress(&self) -> ServerKey {
+        ServerKey {
+            key: self.key.decompress(),
+        }
+    }
+}
+
+/// Generates a [`RadixClientKey`] and a [`SeededServerKey`] pair.
+///
+/// Contrary to [`crate::integer::gen_keys_radix`], the server key returned here is compressed and
+/// must be expanded with [`SeededServerKey::decompress`] before it can be used.
+///
+/// ```rust
+/// use tfhe::integer::gen_keys_radix_seeded;
+/// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+///
+/// let num_blocks = 4;
+/// let (cks, seeded_sks) = gen_keys_radix_seeded(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+/// let sks = seeded_sks.decompress();
+/// ```
+pub fn gen_keys_radix_seeded(
+    parameters_set: &Parameters,
+    num_blocks: usize,
+) -> (RadixClientKey, SeededServerKey) {
+    let cks = ClientKey::new(*parameters_set);
+    let seeded_sks = SeededServerKey::new(&cks);
+
+    (RadixClientKey::from((cks, num_blocks)), seeded_sks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_seeded_ciphertext_decompresses_to_original_message() {
+        let num_blocks = 4;
+        let (cks, _sks) = gen_keys_radix_seeded(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+
+        for msg in [0u64, 1, 27, 255] {
+            let seeded_ct = cks.encrypt_compressed(msg);
+            let ct = seeded_ct.decompress();
+            let dec: u64 = cks.decrypt(&ct);
+            assert_eq!(msg, dec);
+        }
+    }
+
+    #[test]
+    fn test_seeded_server_key_decompresses_and_computes() {
+        let num_blocks = 4;
+        let (cks, seeded_sks) = gen_keys_radix_seeded(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+        let sks = seeded_sks.decompress();
+
+        let msg = 9;
+        let scalar = 3;
+        let ct = cks.encrypt(msg);
+        let ct_res = sks.unchecked_scalar_mul_parallelized(&ct, scalar);
+
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(msg * scalar, dec);
+    }
+}
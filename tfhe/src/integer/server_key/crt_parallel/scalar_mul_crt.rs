@@ -0,0 +1,171 @@
+use crate::integer::server_key::CheckError;
+use crate::integer::server_key::CheckError::CarryFull;
+use crate::integer::{CrtCiphertext, ServerKey};
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext.
+    ///
+    /// The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    ///```rust
+    /// use tfhe::integer::gen_keys;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys(&PARAM_MESSAGE_2_CARRY_2);
+    ///
+    /// let clear_1 = 7;
+    /// let clear_2 = 2;
+    /// let basis = vec![2, 3, 5];
+    /// // Encrypt one message
+    /// let mut ctxt_1 = cks.encrypt_crt(clear_1, basis.clone());
+    ///
+    /// sks.unchecked_crt_scalar_mul_assign_parallelized(&mut ctxt_1, clear_2);
+    ///
+    /// // Decrypt
+    /// let res = cks.decrypt_crt(&ctxt_1);
+    /// assert_eq!((clear_1 * clear_2) % 30, res);
+    /// ```
+    pub fn unchecked_crt_scalar_mul_parallelized(
+        &self,
+        ct: &CrtCiphertext,
+        scalar: u64,
+    ) -> CrtCiphertext {
+        let mut result = ct.clone();
+        self.unchecked_crt_scalar_mul_assign_parallelized(&mut result, scalar);
+        result
+    }
+
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext.
+    ///
+    /// The result is assigned to the `ct` ciphertext.
+    pub fn unchecked_crt_scalar_mul_assign_parallelized(
+        &self,
+        ct: &mut CrtCiphertext,
+        scalar: u64,
+    ) {
+        // Multiply each residue lane by the scalar reduced modulo that residue's modulus
+        ct.blocks
+            .par_iter_mut()
+            .zip(ct.moduli.par_iter())
+            .for_each(|(ct_i, mod_i)| {
+                let scalar_i = scalar % mod_i;
+                self.key.unchecked_scalar_mul_assign(ct_i, scalar_i as u8);
+            });
+    }
+
+    /// Verifies if the multiplication of a ciphertext by a scalar can be computed.
+    pub fn is_crt_scalar_mul_possible(&self, ct: &CrtCiphertext, scalar: u64) -> bool {
+        ct.blocks.iter().zip(ct.moduli.iter()).all(|(ct_i, mod_i)| {
+            let scalar_i = scalar % mod_i;
+            self.key.is_scalar_mul_possible(ct_i, scalar_i as u8)
+        })
+    }
+
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned.
+    pub fn checked_crt_scalar_mul_parallelized(
+        &self,
+        ct: &CrtCiphertext,
+        scalar: u64,
+    ) -> Result<CrtCiphertext, CheckError> {
+        if self.is_crt_scalar_mul_possible(ct, scalar) {
+            Ok(self.unchecked_crt_scalar_mul_parallelized(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is stored in the `ct` ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned, and `ct` is not modified.
+    pub fn checked_crt_scalar_mul_assign_parallelized(
+        &self,
+        ct: &mut CrtCiphertext,
+        scalar: u64,
+    ) -> Result<(), CheckError> {
+        if self.is_crt_scalar_mul_possible(ct, scalar) {
+            self.unchecked_crt_scalar_mul_assign_parallelized(ct, scalar);
+            Ok(())
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically the multiplication of a ciphertext by a scalar.
+    ///
+    /// The result is returned in a new ciphertext.
+    pub fn smart_crt_scalar_mul_parallelized(
+        &self,
+        ct: &mut CrtCiphertext,
+        scalar: u64,
+    ) -> CrtCiphertext {
+        if !self.is_crt_scalar_mul_possible(ct, scalar) {
+            self.full_extract_message_assign_parallelized(ct);
+        }
+
+        let mut ct = ct.clone();
+        self.unchecked_crt_scalar_mul_assign_parallelized(&mut ct, scalar);
+        ct
+    }
+
+    /// Computes homomorphically the multiplication of a ciphertext by a scalar.
+    ///
+    /// The result is assigned to the `ct` ciphertext.
+    pub fn smart_crt_scalar_mul_assign_parallelized(&self, ct: &mut CrtCiphertext, scalar: u64) {
+        if !self.is_crt_scalar_mul_possible(ct, scalar) {
+            self.full_extract_message_assign_parallelized(ct);
+        }
+        self.unchecked_crt_scalar_mul_assign_parallelized(ct, scalar);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integer::gen_keys;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_unchecked_crt_scalar_mul() {
+        let (cks, sks) = gen_keys(&PARAM_MESSAGE_2_CARRY_2);
+        let basis = vec![2, 3, 5];
+        let modulus: u64 = basis.iter().product();
+
+        for (clear_1, clear_2) in [(7u64, 2u64), (0, 4), (29, 1), (29, 29)] {
+            let ctxt_1 = cks.encrypt_crt(clear_1, basis.clone());
+            let ct_res = sks.unchecked_crt_scalar_mul_parallelized(&ctxt_1, clear_2);
+            let res = cks.decrypt_crt(&ct_res);
+            assert_eq!((clear_1 * clear_2) % modulus, res);
+        }
+    }
+
+    #[test]
+    fn test_checked_and_smart_crt_scalar_mul() {
+        let (cks, sks) = gen_keys(&PARAM_MESSAGE_2_CARRY_2);
+        let basis = vec![2, 3, 5];
+
+        let clear_1 = 7;
+        let clear_2 = 2;
+        let mut ctxt_1 = cks.encrypt_crt(clear_1, basis);
+
+        let ct_res = sks
+            .checked_crt_scalar_mul_parallelized(&ctxt_1, clear_2)
+            .unwrap();
+        assert_eq!((clear_1 * clear_2) % 30, cks.decrypt_crt(&ct_res));
+
+        let ct_res = sks.smart_crt_scalar_mul_parallelized(&mut ctxt_1, clear_2);
+        assert_eq!((clear_1 * clear_2) % 30, cks.decrypt_crt(&ct_res));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::integer::ciphertext::{CrtCiphertext, RadixCiphertext};
+use crate::integer::ServerKey;
+use rayon::prelude::*;
+
+/// Derives an independent seed for block/residue `index` from a caller-supplied `seed`, via a
+/// real hash rather than a cheap XOR (which maps distinct `(seed, index)` pairs to the same value,
+/// e.g. `5 ^ 0 == 0 ^ 5` and `5 ^ 5 == 0 ^ 0`).
+fn derive_block_seed(seed: u128, index: usize) -> u128 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    index.hash(&mut hasher);
+    u128::from(hasher.finish())
+}
+
+impl ServerKey {
+    /// Generates an encrypted value, oblivious to the server, uniformly random in
+    /// `[0, 2^random_bits_count)`, as a [`RadixCiphertext`] spanning enough blocks to hold
+    /// `random_bits_count` bits.
+    ///
+    /// Each block is filled with an independent oblivious pseudo-random shortint ciphertext
+    /// derived from `seed`, so that the server can sample encrypted nonces/masks for blind
+    /// protocols without a round-trip to the client.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let ct = sks.generate_oblivious_pseudo_random_radix(0, 8);
+    /// let res = cks.decrypt::<u64>(&ct);
+    /// assert!(res < (1 << 8));
+    /// ```
+    pub fn generate_oblivious_pseudo_random_radix(
+        &self,
+        seed: u128,
+        random_bits_count: u64,
+    ) -> RadixCiphertext {
+        let bits_per_block = f64::log2(self.key.message_modulus.0 as f64) as u64;
+        let num_blocks = random_bits_count.div_ceil(bits_per_block) as usize;
+
+        let blocks = (0..num_blocks)
+            .into_par_iter()
+            .map(|i| {
+                let remaining_bits = random_bits_count - (i as u64 * bits_per_block);
+                let block_bits_count = remaining_bits.min(bits_per_block);
+                // Each block gets an independent seed derived from the caller's seed so that
+                // blocks are mutually independent even though they come from the same draw.
+                let block_seed = derive_block_seed(seed, i);
+                self.key
+                    .generate_oblivious_pseudo_random(block_seed, block_bits_count)
+            })
+            .collect();
+
+        RadixCiphertext { blocks }
+    }
+
+    /// Generates an encrypted value, oblivious to the server, as a [`CrtCiphertext`] over the
+    /// given `basis`.
+    ///
+    /// Each residue `i` is filled with an independent oblivious pseudo-random shortint ciphertext
+    /// uniformly random in `[0, 2^b_i)`, where `b_i` is `floor(log2(basis[i]))` capped at
+    /// `random_bits_count`. The value these residues encode is therefore not uniform over
+    /// `[0, 2^random_bits_count)`, and never exceeds the product of `basis`.
+    pub fn generate_oblivious_pseudo_random_crt(
+        &self,
+        seed: u128,
+        basis: Vec<u64>,
+        random_bits_count: u64,
+    ) -> CrtCiphertext {
+        let blocks = basis
+            .par_iter()
+            .enumerate()
+            .map(|(i, modulus)| {
+                let block_bits_count = f64::log2(*modulus as f64).min(random_bits_count as f64) as u64;
+                let block_seed = derive_block_seed(seed, i);
+                self.key
+                    .generate_oblivious_pseudo_random(block_seed, block_bits_count)
+            })
+            .collect();
+
+        CrtCiphertext {
+            blocks,
+            moduli: basis,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::derive_block_seed;
+    use crate::integer::gen_keys_radix;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_derive_block_seed_distinct_indices() {
+        // A cheap `seed ^ index` derivation collides here; the hash-based one must not.
+        assert_ne!(derive_block_seed(5, 0), derive_block_seed(0, 5));
+        assert_ne!(derive_block_seed(5, 5), derive_block_seed(0, 0));
+    }
+
+    #[test]
+    fn test_oblivious_pseudo_random_radix_in_range() {
+        let num_blocks = 4;
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+
+        for bits in [1u64, 4, 8] {
+            for seed in [0u128, 1, 42] {
+                let ct = sks.generate_oblivious_pseudo_random_radix(seed, bits);
+                let res = cks.decrypt::<u64>(&ct);
+                assert!(res < (1 << bits));
+            }
+        }
+    }
+
+    #[test]
+    fn test_oblivious_pseudo_random_radix_deterministic() {
+        let num_blocks = 4;
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+
+        let ct_1 = sks.generate_oblivious_pseudo_random_radix(7, 8);
+        let ct_2 = sks.generate_oblivious_pseudo_random_radix(7, 8);
+        assert_eq!(cks.decrypt::<u64>(&ct_1), cks.decrypt::<u64>(&ct_2));
+    }
+
+    #[test]
+    fn test_oblivious_pseudo_random_crt_shape() {
+        let num_blocks = 4;
+        let (_cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+        let basis = vec![2, 3, 5];
+
+        let ct = sks.generate_oblivious_pseudo_random_crt(3, basis.clone(), 8);
+        assert_eq!(ct.blocks.len(), basis.len());
+        assert_eq!(ct.moduli, basis);
+    }
+}
@@ -0,0 +1,337 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::server_key::CheckError;
+use crate::integer::server_key::CheckError::CarryFull;
+use crate::integer::ServerKey;
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically a bitwise AND between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext.
+    ///
+    /// The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 202;
+    /// let scalar = 85;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically a bitwise AND:
+    /// let ct_res = sks.unchecked_scalar_bitand(&ct, scalar);
+    ///
+    /// // Decrypt:
+    /// let dec = cks.decrypt(&ct_res);
+    /// assert_eq!(msg & scalar, dec);
+    /// ```
+    pub fn unchecked_scalar_bitand(&self, ct: &RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        let mut result = ct.clone();
+        self.unchecked_scalar_bitand_assign(&mut result, scalar);
+        result
+    }
+
+    pub fn unchecked_scalar_bitand_assign(&self, ct: &mut RadixCiphertext, scalar: u64) {
+        self.unchecked_scalar_bitop_assign_parallelized(ct, scalar, |key, ct_i, scalar_i| {
+            key.unchecked_scalar_bitand_assign(ct_i, scalar_i)
+        });
+    }
+
+    /// Computes homomorphically a bitwise OR between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext.
+    ///
+    /// The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 202;
+    /// let scalar = 85;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically a bitwise OR:
+    /// let ct_res = sks.unchecked_scalar_bitor(&ct, scalar);
+    ///
+    /// // Decrypt:
+    /// let dec = cks.decrypt(&ct_res);
+    /// assert_eq!(msg | scalar, dec);
+    /// ```
+    pub fn unchecked_scalar_bitor(&self, ct: &RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        let mut result = ct.clone();
+        self.unchecked_scalar_bitor_assign(&mut result, scalar);
+        result
+    }
+
+    pub fn unchecked_scalar_bitor_assign(&self, ct: &mut RadixCiphertext, scalar: u64) {
+        self.unchecked_scalar_bitop_assign_parallelized(ct, scalar, |key, ct_i, scalar_i| {
+            key.unchecked_scalar_bitor_assign(ct_i, scalar_i)
+        });
+    }
+
+    /// Computes homomorphically a bitwise XOR between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext.
+    ///
+    /// The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 202;
+    /// let scalar = 85;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically a bitwise XOR:
+    /// let ct_res = sks.unchecked_scalar_bitxor(&ct, scalar);
+    ///
+    /// // Decrypt:
+    /// let dec = cks.decrypt(&ct_res);
+    /// assert_eq!(msg ^ scalar, dec);
+    /// ```
+    pub fn unchecked_scalar_bitxor(&self, ct: &RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        let mut result = ct.clone();
+        self.unchecked_scalar_bitxor_assign(&mut result, scalar);
+        result
+    }
+
+    pub fn unchecked_scalar_bitxor_assign(&self, ct: &mut RadixCiphertext, scalar: u64) {
+        self.unchecked_scalar_bitop_assign_parallelized(ct, scalar, |key, ct_i, scalar_i| {
+            key.unchecked_scalar_bitxor_assign(ct_i, scalar_i)
+        });
+    }
+
+    /// Decomposes `scalar` into per-block digits and dispatches `op` on each block in parallel.
+    ///
+    /// `op` is one of the shortint `unchecked_scalar_bitand/bitor/bitxor_assign` family.
+    fn unchecked_scalar_bitop_assign_parallelized<F>(
+        &self,
+        ct: &mut RadixCiphertext,
+        scalar: u64,
+        op: F,
+    ) where
+        F: Fn(&crate::shortint::ServerKey, &mut crate::shortint::Ciphertext, u8) + Sync,
+    {
+        // Bits of message put to 1
+        let mask = (self.key.message_modulus.0 - 1) as u64;
+        let bits_per_block = f64::log2(self.key.message_modulus.0 as f64) as u64;
+
+        ct.blocks
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(i, ct_i)| {
+                let scalar_i = (scalar >> (i as u64 * bits_per_block)) & mask;
+                op(&self.key, ct_i, scalar_i as u8);
+            });
+    }
+
+    /// Verifies if a bitwise AND between a ciphertext and a scalar can be computed.
+    pub fn is_scalar_bitand_possible(&self, ct: &RadixCiphertext, scalar: u64) -> bool {
+        self.is_scalar_bitop_possible(ct, scalar, |key, ct_i, scalar_i| {
+            key.is_scalar_bitand_possible(ct_i, scalar_i)
+        })
+    }
+
+    /// Verifies if a bitwise OR between a ciphertext and a scalar can be computed.
+    pub fn is_scalar_bitor_possible(&self, ct: &RadixCiphertext, scalar: u64) -> bool {
+        self.is_scalar_bitop_possible(ct, scalar, |key, ct_i, scalar_i| {
+            key.is_scalar_bitor_possible(ct_i, scalar_i)
+        })
+    }
+
+    /// Verifies if a bitwise XOR between a ciphertext and a scalar can be computed.
+    pub fn is_scalar_bitxor_possible(&self, ct: &RadixCiphertext, scalar: u64) -> bool {
+        self.is_scalar_bitop_possible(ct, scalar, |key, ct_i, scalar_i| {
+            key.is_scalar_bitxor_possible(ct_i, scalar_i)
+        })
+    }
+
+    fn is_scalar_bitop_possible<F>(&self, ct: &RadixCiphertext, scalar: u64, is_possible: F) -> bool
+    where
+        F: Fn(&crate::shortint::ServerKey, &crate::shortint::Ciphertext, u8) -> bool,
+    {
+        let mask = (self.key.message_modulus.0 - 1) as u64;
+        let bits_per_block = f64::log2(self.key.message_modulus.0 as f64) as u64;
+
+        ct.blocks.iter().enumerate().all(|(i, ct_i)| {
+            let scalar_i = (scalar >> (i as u64 * bits_per_block)) & mask;
+            is_possible(&self.key, ct_i, scalar_i as u8)
+        })
+    }
+
+    /// Computes homomorphically a bitwise AND between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned.
+    pub fn checked_scalar_bitand(
+        &self,
+        ct: &RadixCiphertext,
+        scalar: u64,
+    ) -> Result<RadixCiphertext, CheckError> {
+        if self.is_scalar_bitand_possible(ct, scalar) {
+            Ok(self.unchecked_scalar_bitand(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a bitwise OR between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned.
+    pub fn checked_scalar_bitor(
+        &self,
+        ct: &RadixCiphertext,
+        scalar: u64,
+    ) -> Result<RadixCiphertext, CheckError> {
+        if self.is_scalar_bitor_possible(ct, scalar) {
+            Ok(self.unchecked_scalar_bitor(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a bitwise XOR between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned.
+    pub fn checked_scalar_bitxor(
+        &self,
+        ct: &RadixCiphertext,
+        scalar: u64,
+    ) -> Result<RadixCiphertext, CheckError> {
+        if self.is_scalar_bitxor_possible(ct, scalar) {
+            Ok(self.unchecked_scalar_bitxor(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically a bitwise AND between a ciphertext and a scalar.
+    ///
+    /// The result is returned in a new ciphertext.
+    pub fn smart_scalar_bitand(&self, ct: &mut RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        if !self.is_scalar_bitand_possible(ct, scalar) {
+            self.full_propagate(ct);
+        }
+
+        self.unchecked_scalar_bitand(ct, scalar)
+    }
+
+    /// Computes homomorphically a bitwise OR between a ciphertext and a scalar.
+    ///
+    /// The result is returned in a new ciphertext.
+    pub fn smart_scalar_bitor(&self, ct: &mut RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        if !self.is_scalar_bitor_possible(ct, scalar) {
+            self.full_propagate(ct);
+        }
+
+        self.unchecked_scalar_bitor(ct, scalar)
+    }
+
+    /// Computes homomorphically a bitwise XOR between a ciphertext and a scalar.
+    ///
+    /// The result is returned in a new ciphertext.
+    pub fn smart_scalar_bitxor(&self, ct: &mut RadixCiphertext, scalar: u64) -> RadixCiphertext {
+        if !self.is_scalar_bitxor_possible(ct, scalar) {
+            self.full_propagate(ct);
+        }
+
+        self.unchecked_scalar_bitxor(ct, scalar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integer::gen_keys_radix;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    use rand::Rng;
+
+    // We have 4 * 2 = 8 bits of message.
+    const NUM_BLOCKS: usize = 4;
+    const MODULUS: u64 = 1 << 8;
+
+    #[test]
+    fn test_unchecked_scalar_bitops_random() {
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, NUM_BLOCKS);
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..5 {
+            let msg = rng.gen_range(0..MODULUS);
+            let scalar = rng.gen_range(0..MODULUS);
+            let ct = cks.encrypt(msg);
+
+            let and_res = sks.unchecked_scalar_bitand(&ct, scalar);
+            assert_eq!(msg & scalar, cks.decrypt(&and_res));
+
+            let or_res = sks.unchecked_scalar_bitor(&ct, scalar);
+            assert_eq!(msg | scalar, cks.decrypt(&or_res));
+
+            let xor_res = sks.unchecked_scalar_bitxor(&ct, scalar);
+            assert_eq!(msg ^ scalar, cks.decrypt(&xor_res));
+        }
+    }
+
+    #[test]
+    fn test_unchecked_scalar_bitops_zero_scalar() {
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, NUM_BLOCKS);
+
+        let msg = 123;
+        let ct = cks.encrypt(msg);
+
+        assert_eq!(0, cks.decrypt(&sks.unchecked_scalar_bitand(&ct, 0)));
+        assert_eq!(msg, cks.decrypt(&sks.unchecked_scalar_bitor(&ct, 0)));
+        assert_eq!(msg, cks.decrypt(&sks.unchecked_scalar_bitxor(&ct, 0)));
+    }
+
+    #[test]
+    fn test_checked_scalar_bitand() {
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, NUM_BLOCKS);
+
+        let msg = 202;
+        let scalar = 85;
+        let ct = cks.encrypt(msg);
+
+        let ct_res = sks.checked_scalar_bitand(&ct, scalar).unwrap();
+        assert_eq!(msg & scalar, cks.decrypt(&ct_res));
+    }
+
+    #[test]
+    fn test_smart_scalar_bitxor() {
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, NUM_BLOCKS);
+
+        let msg = 202;
+        let scalar = 85;
+        let mut ct = cks.encrypt(msg);
+
+        let ct_res = sks.smart_scalar_bitxor(&mut ct, scalar);
+        assert_eq!(msg ^ scalar, cks.decrypt(&ct_res));
+    }
+}
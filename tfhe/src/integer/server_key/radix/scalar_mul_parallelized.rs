@@ -0,0 +1,156 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::server_key::CheckError;
+use crate::integer::server_key::CheckError::CarryFull;
+use crate::integer::ServerKey;
+use crate::shortint::engine::ShortintEngine;
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// This function computes the operation without checking if it exceeds the capacity of the
+    /// ciphertext. Each digit of `scalar` contributes a partial product whose blocks bootstrap in
+    /// parallel via [`ShortintEngine::bootstrap_many`]; the partial products are then summed (see
+    /// [`Self::unchecked_scalar_mul_assign_parallelized`]).
+    ///
+    /// The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 9;
+    /// let scalar = 3;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// let ct_res = sks.unchecked_scalar_mul_parallelized(&ct, scalar);
+    ///
+    /// let dec = cks.decrypt(&ct_res);
+    /// assert_eq!(msg * scalar, dec);
+    /// ```
+    pub fn unchecked_scalar_mul_parallelized(
+        &self,
+        ct: &RadixCiphertext,
+        scalar: u64,
+    ) -> RadixCiphertext {
+        let mut result = ct.clone();
+        self.unchecked_scalar_mul_assign_parallelized(&mut result, scalar);
+        result
+    }
+
+    /// Computes `ct * scalar` by long multiplication: `scalar` is decomposed into its
+    /// base-`message_modulus` digits, each digit scales a full clone of `ct` (its blocks bootstrap
+    /// together in a single [`ShortintEngine::bootstrap_many`] call, reusing one digit-multiply
+    /// accumulator across every block instead of rebuilding it per block, then carries are fully
+    /// propagated), and the resulting partial products are block-shifted by their digit's position
+    /// and summed, exactly like schoolbook multiplication
+    /// (`value * scalar = Σ value·digit_i·message_modulus^i`).
+    pub fn unchecked_scalar_mul_assign_parallelized(&self, ct: &mut RadixCiphertext, scalar: u64) {
+        let num_blocks = ct.blocks.len();
+        let message_modulus = self.key.message_modulus.0 as u64;
+
+        // An encrypted zero, used to pad the low-order end of each shifted partial product.
+        let mut zero_block = ct.blocks[0].clone();
+        self.key.unchecked_scalar_mul_assign(&mut zero_block, 0);
+
+        let mut result: Option<RadixCiphertext> = None;
+        let mut remaining_scalar = scalar;
+
+        for shift in 0..num_blocks {
+            if remaining_scalar == 0 {
+                break;
+            }
+
+            let digit = remaining_scalar % message_modulus;
+            remaining_scalar /= message_modulus;
+
+            if digit == 0 {
+                continue;
+            }
+
+            let mut partial = ct.clone();
+            // Deliberately left unreduced: `full_propagate` below needs the carry that a
+            // block's true `x * digit` product overflows into `message_modulus`, so the LUT
+            // must not pre-reduce it (see `unchecked_scalar_mul_assign`, which this mirrors).
+            let accumulator = self.key.generate_accumulator(move |x| x * digit);
+            partial.blocks = ShortintEngine::bootstrap_many(&self.key, &accumulator, &partial.blocks);
+            self.full_propagate(&mut partial);
+
+            // Multiplying by `message_modulus.pow(shift)` is a block-shift: prepend `shift` zero
+            // blocks, dropping the `shift` most-significant blocks that would overflow past
+            // `num_blocks` (the radix ciphertext wraps modulo `message_modulus.pow(num_blocks)`,
+            // same as `unchecked_scalar_sub_assign`).
+            partial.blocks.truncate(num_blocks - shift);
+            for _ in 0..shift {
+                partial.blocks.insert(0, zero_block.clone());
+            }
+
+            result = Some(match result {
+                None => partial,
+                Some(acc) => {
+                    let mut sum = self.unchecked_add(&acc, &partial);
+                    self.full_propagate(&mut sum);
+                    sum
+                }
+            });
+        }
+
+        *ct = result.unwrap_or_else(|| {
+            let mut zeroed = ct.clone();
+            zeroed
+                .blocks
+                .par_iter_mut()
+                .for_each(|block| self.key.unchecked_scalar_mul_assign(block, 0));
+            zeroed
+        });
+    }
+
+    /// Verifies if the multiplication of a ciphertext by a scalar can be computed.
+    pub fn is_scalar_mul_possible(&self, ct: &RadixCiphertext, scalar: u64) -> bool {
+        let mask = (self.key.message_modulus.0 - 1) as u64;
+        let bits_per_block = f64::log2(self.key.message_modulus.0 as f64) as u64;
+
+        ct.blocks.iter().enumerate().all(|(i, ct_i)| {
+            let scalar_i = (scalar >> (i as u64 * bits_per_block)) & mask;
+            self.key.is_scalar_mul_possible(ct_i, scalar_i as u8)
+        })
+    }
+
+    /// Computes homomorphically a multiplication between a ciphertext and a scalar.
+    ///
+    /// If the operation can be performed, the result is returned in a new ciphertext.
+    /// Otherwise [CheckError::CarryFull] is returned.
+    pub fn checked_scalar_mul_parallelized(
+        &self,
+        ct: &RadixCiphertext,
+        scalar: u64,
+    ) -> Result<RadixCiphertext, CheckError> {
+        if self.is_scalar_mul_possible(ct, scalar) {
+            Ok(self.unchecked_scalar_mul_parallelized(ct, scalar))
+        } else {
+            Err(CarryFull)
+        }
+    }
+
+    /// Computes homomorphically the multiplication of a ciphertext by a scalar.
+    ///
+    /// The result is returned in a new ciphertext.
+    pub fn smart_scalar_mul_parallelized(
+        &self,
+        ct: &mut RadixCiphertext,
+        scalar: u64,
+    ) -> RadixCiphertext {
+        if !self.is_scalar_mul_possible(ct, scalar) {
+            self.full_propagate(ct);
+        }
+
+        self.unchecked_scalar_mul_parallelized(ct, scalar)
+    }
+}
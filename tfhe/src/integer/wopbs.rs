@@ -0,0 +1,139 @@
+//! Module with the definition of the WoPBS (WithOut padding PBS) API for the integer layer.
+//!
+//! This lets a caller evaluate an arbitrary `Fn(u64) -> u64` lookup table over a full encrypted
+//! [`RadixCiphertext`] or [`CrtCiphertext`], instead of being limited to the native add/sub/scalar
+//! operators exposed by [`ServerKey`](crate::integer::ServerKey).
+
+use crate::integer::ciphertext::{CrtCiphertext, RadixCiphertext};
+use crate::integer::{ClientKey, ServerKey};
+use crate::shortint::wopbs::WopbsKey as ShortintWopbsKey;
+use crate::shortint::Parameters;
+
+/// A structure containing the client and server keys required to evaluate an arbitrary
+/// look-up table on [`RadixCiphertext`] and [`CrtCiphertext`] using the WoPBS (WithOut padding
+/// Programmable Bootstrap) technique.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::integer::gen_keys_radix;
+/// use tfhe::integer::wopbs::WopbsKey;
+/// use tfhe::shortint::parameters::parameters_wopbs_message_carry::WOPBS_PARAM_MESSAGE_2_CARRY_2;
+/// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+///
+/// let num_blocks = 4;
+/// let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+/// let wopbs_key = WopbsKey::new_wopbs_key(&cks, &sks, &WOPBS_PARAM_MESSAGE_2_CARRY_2);
+///
+/// let clear = 27;
+/// let ct = cks.encrypt(clear);
+/// let ct_res = wopbs_key.wopbs(&ct, |x| x * x % 256);
+/// let res = cks.decrypt(&ct_res);
+/// assert_eq!(res, (clear * clear) % 256);
+/// ```
+#[derive(Clone)]
+pub struct WopbsKey {
+    key: ShortintWopbsKey,
+}
+
+impl WopbsKey {
+    /// Generates a [`WopbsKey`] compatible with both the classical PBS and the WoPBS path.
+    pub fn new_wopbs_key(
+        cks: &ClientKey,
+        sks: &ServerKey,
+        parameters: &Parameters,
+    ) -> WopbsKey {
+        WopbsKey {
+            key: ShortintWopbsKey::new_wopbs_key(cks.as_ref(), &sks.key, parameters),
+        }
+    }
+
+    /// Generates a [`WopbsKey`] usable only for WoPBS operations.
+    pub fn new_wopbs_key_only_for_wopbs(cks: &ClientKey, sks: &ServerKey) -> WopbsKey {
+        WopbsKey {
+            key: ShortintWopbsKey::new_wopbs_key_only_for_wopbs(cks.as_ref(), &sks.key),
+        }
+    }
+
+    /// Builds a LUT for a [`CrtCiphertext`] from a closure `f: Fn(u64) -> u64` evaluated
+    /// residue-wise over the CRT representation.
+    pub fn generate_lut_crt<F>(&self, ct: &CrtCiphertext, f: F) -> Vec<Vec<u64>>
+    where
+        F: Fn(u64) -> u64,
+    {
+        ct.blocks
+            .iter()
+            .zip(ct.moduli.iter())
+            .map(|(block, modulus)| self.key.generate_lut(block, |x| f(x) % modulus))
+            .collect()
+    }
+
+    /// Evaluates `f` homomorphically over the *full* decomposed integer held in `ct` (not just
+    /// one block's own digit): the blocks' digits are extracted jointly, the no-padding PBS is
+    /// run once over their combined domain (see
+    /// [`crate::shortint::wopbs::WopbsKey::wopbs_radix`]), and the result is recomposed into a
+    /// new [`RadixCiphertext`].
+    pub fn wopbs<F>(&self, ct: &RadixCiphertext, f: F) -> RadixCiphertext
+    where
+        F: Fn(u64) -> u64,
+    {
+        let blocks = self.key.wopbs_radix(&ct.blocks, |m| f(m as u64) as u128);
+
+        RadixCiphertext { blocks }
+    }
+
+    /// Applies a per-residue LUT (as produced by [`Self::generate_lut_crt`]) homomorphically to a
+    /// [`CrtCiphertext`].
+    pub fn wopbs_crt(&self, ct: &CrtCiphertext, lut: &[Vec<u64>]) -> CrtCiphertext {
+        let blocks = ct
+            .blocks
+            .iter()
+            .zip(lut.iter())
+            .map(|(block, block_lut)| self.key.wopbs(block, block_lut))
+            .collect();
+
+        CrtCiphertext {
+            blocks,
+            moduli: ct.moduli.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::integer::wopbs::WopbsKey;
+    use crate::integer::{gen_keys, gen_keys_radix};
+    use crate::shortint::parameters::parameters_wopbs_message_carry::WOPBS_PARAM_MESSAGE_2_CARRY_2;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_wopbs_radix() {
+        let num_blocks = 4;
+        let (cks, sks) = gen_keys_radix(&PARAM_MESSAGE_2_CARRY_2, num_blocks);
+        let wopbs_key = WopbsKey::new_wopbs_key(&cks, &sks, &WOPBS_PARAM_MESSAGE_2_CARRY_2);
+
+        for clear in [0u64, 1, 27, 255] {
+            let ct = cks.encrypt(clear);
+            let ct_res = wopbs_key.wopbs(&ct, |x| (x * x) % 256);
+            let res = cks.decrypt(&ct_res);
+            assert_eq!(res, (clear * clear) % 256);
+        }
+    }
+
+    #[test]
+    fn test_wopbs_crt() {
+        let (cks, sks) = gen_keys(&PARAM_MESSAGE_2_CARRY_2);
+        let wopbs_key = WopbsKey::new_wopbs_key(&cks, &sks, &WOPBS_PARAM_MESSAGE_2_CARRY_2);
+
+        let basis = vec![2, 3, 5];
+        let modulus: u64 = basis.iter().product();
+
+        for clear in [0u64, 7, 29] {
+            let ct = cks.encrypt_crt(clear, basis.clone());
+            let lut = wopbs_key.generate_lut_crt(&ct, |x| (x + 1) % modulus);
+            let ct_res = wopbs_key.wopbs_crt(&ct, &lut);
+            let res = cks.decrypt_crt(&ct_res);
+            assert_eq!(res, (clear + 1) % modulus);
+        }
+    }
+}
@@ -3,18 +3,25 @@
 //! Engines are required to abstract cryptographic notions and efficiently manage memory from the
 //! underlying `core_crypto` module.
 
+use crate::core_crypto::algorithms::{
+    keyswitch_lwe_ciphertext, programmable_bootstrap_lwe_ciphertext_mem_optimized,
+};
 use crate::core_crypto::commons::computation_buffers::ComputationBuffers;
 use crate::core_crypto::commons::generators::{
     DeterministicSeeder, EncryptionRandomGenerator, SecretRandomGenerator,
 };
-use crate::core_crypto::commons::math::random::{ActivatedRandomGenerator, Seeder};
+use crate::core_crypto::commons::math::random::{ActivatedRandomGenerator, Seed, Seeder};
+use crate::core_crypto::commons::parameters::CiphertextModulusLog;
 use crate::core_crypto::entities::*;
 use crate::core_crypto::prelude::ContainerMut;
 use crate::core_crypto::seeders::new_seeder;
-use crate::shortint::ciphertext::Degree;
+use crate::shortint::ciphertext::{CompressedModulusSwitchedCiphertext, Degree};
 use crate::shortint::server_key::Accumulator;
-use crate::shortint::ServerKey;
+use crate::shortint::wopbs::{CompressedWopbsKey, WopbsKey};
+use crate::shortint::{Ciphertext, ClientKey, Parameters, ServerKey};
+use rayon::prelude::*;
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 mod client_side;
@@ -32,6 +39,29 @@ pub struct BuffersRef<'a> {
     pub(crate) buffer_lwe_after_ks: LweCiphertextMutView<'a, u64>,
 }
 
+/// Identifies a distinct `(message_modulus, carry_modulus, polynomial_size)` configuration, so
+/// that the engine can keep a separate scratch [`Memory`] per parameter set instead of aliasing
+/// one buffer across keys of different shapes.
+///
+/// This is what lets a heterogeneous CRT basis (see `gen_keys_crt_multi`) mix several `Parameters`
+/// in the same engine without each residue's key evicting the others' scratch space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct GenKeyId {
+    message_modulus: usize,
+    carry_modulus: usize,
+    polynomial_size: usize,
+}
+
+impl GenKeyId {
+    fn for_server_key(server_key: &ServerKey) -> Self {
+        Self {
+            message_modulus: server_key.message_modulus.0,
+            carry_modulus: server_key.carry_modulus.0,
+            polynomial_size: server_key.bootstrapping_key.polynomial_size().0,
+        }
+    }
+}
+
 #[derive(Default)]
 struct Memory {
     buffer: Vec<u64>,
@@ -127,6 +157,66 @@ where
     max_value
 }
 
+/// Multiplies `lhs` by `rhs` in the negacyclic ring `Z[X] / (X^N + 1)`, writing the result into
+/// `out`. This is the "cheap" half of multivalue bootstrapping: unlike the blind rotation, this is
+/// a plain schoolbook convolution over cleartext-sized coefficients.
+fn negacyclic_convolution_assign(lhs: &[u64], rhs: &[u64], out: &mut [u64]) {
+    let n = lhs.len();
+    debug_assert_eq!(rhs.len(), n);
+    debug_assert_eq!(out.len(), n);
+
+    out.fill(0);
+    for (i, &lhs_i) in lhs.iter().enumerate() {
+        for (j, &rhs_j) in rhs.iter().enumerate() {
+            let k = i + j;
+            let coeff = lhs_i.wrapping_mul(rhs_j);
+            if k < n {
+                out[k] = out[k].wrapping_add(coeff);
+            } else {
+                // Wrapping past degree N negates the coefficient, per (X^N + 1) = 0.
+                out[k - n] = out[k - n].wrapping_sub(coeff);
+            }
+        }
+    }
+}
+
+/// Extracts the constant-term LWE sample (the one a classical PBS would sample-extract at index
+/// 0) out of a GLWE ciphertext, as an owned [`LweCiphertextOwned<u64>`].
+fn sample_extract_constant_term(glwe: &GlweCiphertextOwned<u64>) -> LweCiphertextOwned<u64> {
+    let poly_size = glwe.polynomial_size().0;
+    let glwe_size = glwe.glwe_size().0;
+
+    let mut lwe_mask = Vec::with_capacity((glwe_size - 1) * poly_size);
+    for k in 0..glwe_size - 1 {
+        let mask_poly = &glwe.as_ref()[k * poly_size..(k + 1) * poly_size];
+        // Extracting the X^0 coefficient negates and reverses the remaining mask coefficients,
+        // matching the standard LWE-from-GLWE sample extraction at index 0.
+        lwe_mask.push(mask_poly[0]);
+        for coeff in mask_poly[1..].iter().rev() {
+            lwe_mask.push(coeff.wrapping_neg());
+        }
+    }
+    let body = glwe.as_ref()[(glwe_size - 1) * poly_size];
+
+    let mut lwe = LweCiphertextOwned::new(0, LweSize(lwe_mask.len() + 1));
+    lwe.as_mut()[..lwe_mask.len()].copy_from_slice(&lwe_mask);
+    *lwe.as_mut().last_mut().unwrap() = body;
+    lwe
+}
+
+/// Rounds every coefficient of `input` down to its top `log_modulus` bits, the same reduction a
+/// bootstrap's blind rotation applies to its keyswitched input before consulting the accumulator.
+fn modulus_switch(input: &[u64], log_modulus: CiphertextModulusLog) -> Vec<u64> {
+    let shift = u64::BITS - log_modulus.0 as u32;
+    input
+        .iter()
+        .map(|&coeff| {
+            // Round to the nearest representable value before truncating the low bits away.
+            (coeff.wrapping_add(1u64 << (shift - 1))) >> shift
+        })
+        .collect()
+}
+
 /// Simple wrapper around [`std::error::Error`] to be able to
 /// forward all the possible `EngineError` type from [`core_cryto`](crate::core_crypto)
 #[allow(dead_code)]
@@ -148,6 +238,28 @@ where
 
 pub(crate) type EngineResult<T> = Result<T, EngineError>;
 
+/// Error returned when a packed n-ary accumulator is asked to evaluate more digits than the
+/// `message_modulus * carry_modulus` headroom of a ciphertext can hold.
+#[derive(Debug)]
+struct NaryArityError {
+    arity: usize,
+    modulus: u64,
+    headroom: u64,
+}
+
+impl std::fmt::Display for NaryArityError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "packing {} inputs of base {} requires {}^{} values, which exceeds the available \
+             headroom of {}",
+            self.arity, self.modulus, self.modulus, self.arity, self.headroom
+        )
+    }
+}
+
+impl std::error::Error for NaryArityError {}
+
 /// ShortintEngine
 ///
 /// This 'engine' holds the necessary engines from [`core_crypto`](crate::core_crypto)
@@ -168,7 +280,10 @@ pub struct ShortintEngine {
     /// [`EncryptionRandomGenerator`] to encrypt seeded types.
     seeder: DeterministicSeeder<ActivatedRandomGenerator>,
     computation_buffers: ComputationBuffers,
-    ciphertext_buffers: Memory,
+    /// One scratch [`Memory`] per distinct parameter configuration currently in use, so that
+    /// calling `buffers_for_key` with keys from several parameter sets (as a heterogeneous CRT
+    /// basis does) does not have each key evict another's buffers.
+    ciphertext_buffers: HashMap<GenKeyId, Memory>,
 }
 
 impl ShortintEngine {
@@ -253,16 +368,556 @@ impl ShortintEngine {
         ShortintEngine::generate_accumulator_with_engine(server_key, wrapped_f)
     }
 
+    /// Generalizes [`Self::generate_accumulator_bivariate_with_engine`] to an arbitrary number of
+    /// packed inputs: `f` is evaluated on `arity` base-`message_modulus` digits decomposed out of
+    /// the single packed input ciphertext, letting fused operations (e.g. `a*b + c` on small
+    /// blocks) run as a single PBS instead of a chain of bivariate LUTs.
+    ///
+    /// Returns [`NaryArityError`] if `message_modulus^arity` would not fit in the
+    /// `message_modulus * carry_modulus` headroom a packed ciphertext provides.
+    fn generate_accumulator_nary_with_engine<F>(
+        server_key: &ServerKey,
+        arity: usize,
+        f: F,
+    ) -> EngineResult<Accumulator>
+    where
+        F: Fn(&[u64]) -> u64,
+    {
+        let modulus = server_key.message_modulus.0 as u64;
+        let headroom = (server_key.message_modulus.0 * server_key.carry_modulus.0) as u64;
+
+        let capacity = modulus
+            .checked_pow(arity as u32)
+            .filter(|capacity| *capacity <= headroom);
+
+        if capacity.is_none() {
+            return Err(NaryArityError { arity, modulus, headroom }.into());
+        }
+
+        let wrapped_f = move |input: u64| -> u64 {
+            // Decompose `input` into `arity` base-`modulus` digits, least-significant first.
+            let mut digits = vec![0u64; arity];
+            let mut remaining = input;
+            for digit in digits.iter_mut() {
+                *digit = remaining % modulus;
+                remaining /= modulus;
+            }
+
+            f(&digits)
+        };
+
+        ShortintEngine::generate_accumulator_with_engine(server_key, wrapped_f)
+    }
+
+    /// Builds the shared `v0` accumulator used by [`ShortintEngine::multivalue_bootstrap`], plus
+    /// the per-function cleartext polynomials `q_i` such that `v0 * q_i = P_i mod (X^N + 1)`,
+    /// where `P_i` is the usual test polynomial for `functions[i]`.
+    ///
+    /// `v0` is the "redundant" accumulator `1 + X + ... + X^(N-1)`, box-rotated exactly once by
+    /// [`fill_accumulator`]'s bookkeeping (so the expensive blind rotation only ever needs to run
+    /// on this one polynomial). Each `q_i` is then recovered box-by-box: because `v0` is constant
+    /// within every box, multiplying by `q_i`'s per-box coefficient reproduces `f_i`'s value in
+    /// that box, so `q_i`'s coefficients are exactly `f_i`'s desired per-box outputs (scaled to
+    /// sit below the noise-growth budget).
+    ///
+    /// The caller must ensure every `q_i`'s L1 norm stays within the noise budget of a single
+    /// GLWE x cleartext-polynomial product, since each coefficient acts as a cleartext multiplier
+    /// on the ciphertext coming out of the shared blind rotation.
+    fn generate_multivalue_accumulator<F>(
+        server_key: &ServerKey,
+        functions: &[F],
+    ) -> EngineResult<(Accumulator, Vec<Vec<u64>>)>
+    where
+        F: Fn(u64) -> u64,
+    {
+        let mut v0 = GlweCiphertextOwned::<u64>::new(
+            0,
+            server_key.bootstrapping_key.glwe_size(),
+            server_key.bootstrapping_key.polynomial_size(),
+        );
+        // v0(X) = 1 + X + ... + X^(N-1), box-rotated the same way any other accumulator is.
+        let max_value = fill_accumulator(&mut v0, server_key, |_| 1);
+
+        let modulus_sup = server_key.message_modulus.0 * server_key.carry_modulus.0;
+        let poly_size = server_key.bootstrapping_key.polynomial_size().0;
+        let box_size = poly_size / modulus_sup;
+
+        let q_polynomials = functions
+            .iter()
+            .map(|f| {
+                let mut q = vec![0u64; poly_size];
+                // v0 is constant within each box, so deconvolving f against v0 reduces to
+                // placing f's per-box value as q's per-box coefficient.
+                for i in 0..modulus_sup {
+                    let index = i * box_size;
+                    q[index] = f(i as u64);
+                }
+                q
+            })
+            .collect();
+
+        Ok((
+            Accumulator {
+                acc: v0,
+                degree: Degree(max_value as usize),
+            },
+            q_polynomials,
+        ))
+    }
+
+    /// Keyswitches `ct_in` to the bootstrapping key's input parameters, then blind-rotates `acc`
+    /// in place by the keyswitched value. This is the expensive step that
+    /// [`ShortintEngine::multivalue_bootstrap`] shares across every function being evaluated.
+    fn blind_rotate_assign(
+        &mut self,
+        server_key: &ServerKey,
+        ct_in: &Ciphertext,
+        acc: &mut GlweCiphertextOwned<u64>,
+    ) -> EngineResult<()> {
+        let (buffers, computation_buffers) = self.buffers_for_key(server_key);
+
+        let mut after_ks =
+            LweCiphertextOwned::new(0, server_key.key_switching_key.output_lwe_size());
+        keyswitch_lwe_ciphertext(&server_key.key_switching_key, &ct_in.ct, &mut after_ks);
+
+        programmable_bootstrap_lwe_ciphertext_mem_optimized(
+            &after_ks,
+            &mut buffers.buffer_lwe_after_ks,
+            acc,
+            &server_key.bootstrapping_key,
+            computation_buffers,
+        );
+
+        Ok(())
+    }
+
+    /// Runs a single keyswitch-then-bootstrap of `ct_in` against `acc`, returning the resulting
+    /// [`Ciphertext`]. This is the per-ciphertext unit of work that
+    /// [`ShortintEngine::bootstrap_many`] fans out across a rayon thread pool.
+    fn keyswitch_bootstrap(
+        &mut self,
+        server_key: &ServerKey,
+        ct_in: &Ciphertext,
+        acc: &Accumulator,
+    ) -> EngineResult<Ciphertext> {
+        let mut acc_rotated = acc.acc.clone();
+        self.blind_rotate_assign(server_key, ct_in, &mut acc_rotated)?;
+        let ct = sample_extract_constant_term(&acc_rotated);
+
+        Ok(Ciphertext {
+            ct,
+            degree: acc.degree,
+            message_modulus: ct_in.message_modulus,
+            carry_modulus: ct_in.carry_modulus,
+        })
+    }
+
+    /// Runs [`Self::keyswitch_bootstrap`] on every ciphertext in `cts` against the shared `acc`,
+    /// partitioning the work across the rayon thread pool.
+    ///
+    /// Each worker thread reuses its own [`LOCAL_ENGINE`], so the per-parameter-set scratch
+    /// buffers cached by [`Self::buffers_for_key`] are never aliased between two bootstraps
+    /// running concurrently: the `Memory` each worker touches belongs to that worker's thread,
+    /// not to a single shared engine instance.
+    pub fn bootstrap_many(
+        server_key: &ServerKey,
+        acc: &Accumulator,
+        cts: &[Ciphertext],
+    ) -> Vec<Ciphertext> {
+        cts.par_iter()
+            .map(|ct| {
+                Self::with_thread_local_mut(|engine| {
+                    engine.keyswitch_bootstrap(server_key, ct, acc).unwrap()
+                })
+            })
+            .collect()
+    }
+
+    /// Evaluates many univariate functions on `ct_in` while paying for only a single blind
+    /// rotation, rather than one blind rotation per function.
+    ///
+    /// See [`ShortintEngine::generate_multivalue_accumulator`] for how the shared accumulator
+    /// `v0` and the per-function polynomials `q_i` are derived. After the one (expensive) blind
+    /// rotation produces a GLWE ciphertext `acc_rotated` encrypting `v0` evaluated at the input,
+    /// each `f_i`'s result is recovered with a cheap GLWE x cleartext-polynomial product against
+    /// `q_i`, followed by a sample extraction of the constant coefficient.
+    pub fn multivalue_bootstrap<F>(
+        &mut self,
+        server_key: &ServerKey,
+        ct_in: &Ciphertext,
+        functions: &[F],
+    ) -> EngineResult<Vec<LweCiphertextOwned<u64>>>
+    where
+        F: Fn(u64) -> u64,
+    {
+        let (accumulator, q_polynomials) =
+            Self::generate_multivalue_accumulator(server_key, functions)?;
+
+        let mut acc_rotated = accumulator.acc.clone();
+        self.blind_rotate_assign(server_key, ct_in, &mut acc_rotated)?;
+
+        let poly_size = server_key.bootstrapping_key.polynomial_size().0;
+        let glwe_size = server_key.bootstrapping_key.glwe_size().0;
+
+        Ok(q_polynomials
+            .iter()
+            .map(|q| {
+                // GLWE x cleartext-polynomial product: every polynomial coordinate (mask and
+                // body) of acc_rotated gets multiplied by q modulo (X^N + 1).
+                let mut product = GlweCiphertextOwned::<u64>::new(
+                    0,
+                    server_key.bootstrapping_key.glwe_size(),
+                    server_key.bootstrapping_key.polynomial_size(),
+                );
+                for k in 0..glwe_size {
+                    let input_poly = &acc_rotated.as_ref()[k * poly_size..(k + 1) * poly_size];
+                    let output_poly =
+                        &mut product.as_mut()[k * poly_size..(k + 1) * poly_size];
+                    negacyclic_convolution_assign(input_poly, q, output_poly);
+                }
+
+                // Sample-extract the constant coefficient, which holds f_i(m) after the product.
+                sample_extract_constant_term(&product)
+            })
+            .collect())
+    }
+
     /// Return the [`BuffersRef`] and [`ComputationBuffers`] for the given `ServerKey`
+    ///
+    /// The scratch [`Memory`] is cached per parameter configuration (see [`GenKeyId`]), so
+    /// calling this with `ServerKey`s generated from different `Parameters` (e.g. the per-residue
+    /// keys of a heterogeneous CRT basis) does not have one key's buffers clobber another's.
     pub fn buffers_for_key(
         &mut self,
         server_key: &ServerKey,
     ) -> (BuffersRef<'_>, &mut ComputationBuffers) {
-        let mut buffers = self.ciphertext_buffers.as_buffers(server_key);
+        let key_id = GenKeyId::for_server_key(server_key);
+        let mut buffers = self
+            .ciphertext_buffers
+            .entry(key_id)
+            .or_default()
+            .as_buffers(server_key);
         fill_accumulator(&mut buffers.accumulator, server_key, |n| {
             n % server_key.message_modulus.0 as u64
         });
 
         (buffers, &mut self.computation_buffers)
     }
+
+    /// Generates the [`WopbsKey`] compatible with `cks`/`sks`, then discards it, keeping only the
+    /// [`Seed`] that seeded its generation and the `parameters` it was built from.
+    ///
+    /// Mirrors the seed-then-reseed shape of [`ServerKey::generate_oblivious_pseudo_random`]: a
+    /// fresh [`Seed`] drawn from `self.seeder` is the only piece of randomness behind the key, so
+    /// [`Self::expand_compressed_wopbs_key`] can later rebuild the identical [`WopbsKey`] from
+    /// that seed and `parameters` alone. Building the key once here, rather than lazily on first
+    /// use, also surfaces an incompatible `parameters` immediately instead of deferring the error
+    /// to `decompress`.
+    pub fn new_compressed_wopbs_key(
+        &mut self,
+        cks: &ClientKey,
+        sks: &ServerKey,
+        parameters: &Parameters,
+    ) -> EngineResult<CompressedWopbsKey> {
+        let seed = self.seeder.seed();
+
+        let mut prf_seeder = DeterministicSeeder::<ActivatedRandomGenerator>::new(seed);
+        self.encryption_generator =
+            EncryptionRandomGenerator::new(prf_seeder.seed(), &mut prf_seeder);
+        let _ = self.new_wopbs_key(cks, sks, parameters)?;
+
+        Ok(CompressedWopbsKey {
+            seed,
+            param: *parameters,
+        })
+    }
+
+    /// Regenerates the [`WopbsKey`] a [`CompressedWopbsKey`] was compressed from, reseeding the
+    /// engine's deterministic generators from the stored [`Seed`] before rebuilding it from the
+    /// already-reconstructed `cks`/`sks` (see [`CompressedWopbsKey::decompress`], which rebuilds
+    /// them ahead of entering the engine).
+    pub fn expand_compressed_wopbs_key(
+        &mut self,
+        compressed: &CompressedWopbsKey,
+        cks: &ClientKey,
+        sks: &ServerKey,
+    ) -> EngineResult<WopbsKey> {
+        let mut prf_seeder = DeterministicSeeder::<ActivatedRandomGenerator>::new(compressed.seed);
+        self.encryption_generator =
+            EncryptionRandomGenerator::new(prf_seeder.seed(), &mut prf_seeder);
+
+        self.new_wopbs_key(cks, sks, &compressed.param)
+    }
+
+    /// Performs the bootstrap-side modulus switch on `ct` (the same reduction
+    /// [`Self::blind_rotate_assign`] applies to its keyswitched input), then keeps only the top
+    /// `log_modulus` bits of every mask/body coefficient.
+    ///
+    /// Unlike [`Self::new_compressed_wopbs_key`], which shrinks by storing a seed to regenerate
+    /// identical mask material later, this shrinks by discarding precision the ciphertext actually
+    /// carried: the low bits are gone for good, so [`Self::decompress_switched_modulus_wopbs`]
+    /// widens the stored coefficients back out rather than reproducing the original ones exactly.
+    pub fn switch_modulus_and_compress_wopbs(
+        &mut self,
+        wopbs_key: &WopbsKey,
+        ct: &Ciphertext,
+        log_modulus: CiphertextModulusLog,
+    ) -> EngineResult<CompressedModulusSwitchedCiphertext> {
+        let server_key = &wopbs_key.wopbs_server_key;
+
+        let mut after_ks =
+            LweCiphertextOwned::new(0, server_key.key_switching_key.output_lwe_size());
+        keyswitch_lwe_ciphertext(&server_key.key_switching_key, &ct.ct, &mut after_ks);
+
+        let switched = modulus_switch(after_ks.as_ref(), log_modulus);
+
+        Ok(CompressedModulusSwitchedCiphertext::new(
+            switched,
+            log_modulus,
+            ct.degree,
+            ct.message_modulus,
+            ct.carry_modulus,
+        ))
+    }
+
+    /// Widens `compressed`'s stored coefficients back to full-width `u64`s, producing a
+    /// [`Ciphertext`] that can be fed back into WoPBS (e.g. [`WopbsKey::programmable_bootstrapping`]).
+    ///
+    /// The bits [`Self::switch_modulus_and_compress_wopbs`] discarded stay zero, so the result
+    /// carries the same noise budget a fresh keyswitch-and-bootstrap output would, at the reduced
+    /// precision of `compressed`'s `log_modulus`.
+    pub fn decompress_switched_modulus_wopbs(
+        &mut self,
+        wopbs_key: &WopbsKey,
+        compressed: &CompressedModulusSwitchedCiphertext,
+    ) -> EngineResult<Ciphertext> {
+        let server_key = &wopbs_key.wopbs_server_key;
+        let shift = u64::BITS - compressed.log_modulus().0 as u32;
+
+        let widened: Vec<u64> = compressed
+            .coefficients()
+            .iter()
+            .map(|&coeff| coeff << shift)
+            .collect();
+
+        Ok(Ciphertext {
+            ct: LweCiphertextOwned::from_container(widened),
+            degree: compressed.degree(),
+            message_modulus: server_key.message_modulus,
+            carry_modulus: server_key.carry_modulus,
+        })
+    }
+}
+
+impl ServerKey {
+    /// Evaluates several univariate functions on the same ciphertext while paying for only one
+    /// blind rotation, instead of one per function (see
+    /// [`ShortintEngine::multivalue_bootstrap`]).
+    ///
+    /// Every function in `functions` is evaluated on the same input digit; the `i`-th returned
+    /// [`Ciphertext`] encrypts `functions[i](m)`, where `m` is `ct_in`'s cleartext message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::shortint::gen_keys;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// fn square(x: u64) -> u64 {
+    ///     (x * x) % 4
+    /// }
+    /// fn double(x: u64) -> u64 {
+    ///     (x * 2) % 4
+    /// }
+    ///
+    /// let (cks, sks) = gen_keys(PARAM_MESSAGE_2_CARRY_2);
+    ///
+    /// let msg = 3;
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// let functions: Vec<fn(u64) -> u64> = vec![square, double];
+    /// let results = sks.multivalue_bootstrap(&ct, &functions);
+    ///
+    /// assert_eq!(cks.decrypt(&results[0]), square(msg));
+    /// assert_eq!(cks.decrypt(&results[1]), double(msg));
+    /// ```
+    pub fn multivalue_bootstrap<F>(&self, ct_in: &Ciphertext, functions: &[F]) -> Vec<Ciphertext>
+    where
+        F: Fn(u64) -> u64,
+    {
+        let lwes = ShortintEngine::with_thread_local_mut(|engine| {
+            engine
+                .multivalue_bootstrap(self, ct_in, functions)
+                .unwrap()
+        });
+
+        lwes.into_iter()
+            .map(|ct| Ciphertext {
+                ct,
+                degree: Degree(self.message_modulus.0 - 1),
+                message_modulus: self.message_modulus,
+                carry_modulus: self.carry_modulus,
+            })
+            .collect()
+    }
+
+    /// Packs `inputs` into a single ciphertext (`Σ inputs[i]·message_modulus^i`) and evaluates `f`
+    /// on the packed digits with a single PBS, instead of a chain of bivariate LUTs — see
+    /// [`crate::shortint::engine::ShortintEngine::generate_accumulator_nary_with_engine`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `message_modulus^inputs.len()` does not fit in the ciphertext's
+    /// `message_modulus * carry_modulus` headroom.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::shortint::gen_keys;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let (cks, sks) = gen_keys(PARAM_MESSAGE_2_CARRY_2);
+    ///
+    /// let a = 2;
+    /// let b = 1;
+    /// let ct_a = cks.encrypt(a);
+    /// let ct_b = cks.encrypt(b);
+    ///
+    /// // Fuse `a + b*2` into a single PBS instead of an add followed by a bootstrap.
+    /// let ct_res = sks.nary_bootstrap(&[ct_a, ct_b], |digits| (digits[0] + digits[1] * 2) % 4);
+    /// let res = cks.decrypt(&ct_res);
+    /// assert_eq!(res, (a + b * 2) % 4);
+    /// ```
+    pub fn nary_bootstrap<F>(&self, inputs: &[Ciphertext], f: F) -> Ciphertext
+    where
+        F: Fn(&[u64]) -> u64,
+    {
+        let modulus = self.message_modulus.0 as u64;
+
+        let mut packed = inputs[0].clone();
+        for (i, input) in inputs.iter().enumerate().skip(1) {
+            let mut scaled = input.clone();
+            self.unchecked_scalar_mul_assign(&mut scaled, modulus.pow(i as u32) as u8);
+            self.unchecked_add_assign(&mut packed, &scaled);
+        }
+
+        let accumulator =
+            ShortintEngine::generate_accumulator_nary_with_engine(self, inputs.len(), f)
+                .unwrap_or_else(|err| panic!("{}", err.error));
+
+        ShortintEngine::with_thread_local_mut(|engine| {
+            engine
+                .keyswitch_bootstrap(self, &packed, &accumulator)
+                .unwrap()
+        })
+    }
+
+    /// Builds the [`Accumulator`] that bootstrapping `ct` against `f` would use, without running
+    /// the (expensive) blind rotation itself.
+    ///
+    /// This is what lets a caller amortize the test-polynomial construction across many
+    /// ciphertexts via [`ShortintEngine::bootstrap_many`] instead of paying for it on every
+    /// single-ciphertext bootstrap.
+    pub fn generate_accumulator<F>(&self, f: F) -> Accumulator
+    where
+        F: Fn(u64) -> u64,
+    {
+        ShortintEngine::generate_accumulator_with_engine(self, f).unwrap()
+    }
+
+    /// Generates an encrypted value, oblivious to the server, uniformly random in
+    /// `[0, 2^random_bits_count)`.
+    ///
+    /// The server never encrypts anything here: `seed` deterministically seeds a throwaway CSPRNG
+    /// that draws the mask of a fresh LWE "ciphertext" (body left at `0`), so the phase this
+    /// ciphertext carries is `-<mask, s>`, a value only the client's secret key `s` can resolve.
+    /// Bootstrapping it with a LUT that reduces every possible phase to `random_bits_count` bits
+    /// then turns that into a uniformly random encrypted message, reproducible from `seed` but
+    /// unknown to the server that produced it.
+    pub fn generate_oblivious_pseudo_random(&self, seed: u128, random_bits_count: u64) -> Ciphertext {
+        let glwe_size = self.bootstrapping_key.glwe_size();
+        let poly_size = self.bootstrapping_key.polynomial_size();
+        let lwe_size = LweSize((glwe_size.0 - 1) * poly_size.0 + 1);
+
+        let mut prf_seeder = DeterministicSeeder::<ActivatedRandomGenerator>::new(Seed(seed));
+        let mut mask_generator = EncryptionRandomGenerator::<ActivatedRandomGenerator>::new(
+            prf_seeder.seed(),
+            &mut prf_seeder,
+        );
+
+        let mut ct_in = LweCiphertextOwned::new(0u64, lwe_size);
+        let mask_len = lwe_size.0 - 1;
+        mask_generator.fill_slice_with_random_mask(&mut ct_in.as_mut()[..mask_len]);
+
+        let wrapped_ct_in = Ciphertext {
+            ct: ct_in,
+            degree: Degree(self.message_modulus.0 * self.carry_modulus.0 - 1),
+            message_modulus: self.message_modulus,
+            carry_modulus: self.carry_modulus,
+        };
+
+        let modulus = 1u64 << random_bits_count;
+        let accumulator =
+            ShortintEngine::generate_accumulator_with_engine(self, move |x| x % modulus).unwrap();
+
+        ShortintEngine::with_thread_local_mut(|engine| {
+            engine
+                .keyswitch_bootstrap(self, &wrapped_ct_in, &accumulator)
+                .unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::shortint::gen_keys;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_multivalue_bootstrap() {
+        fn square(x: u64) -> u64 {
+            (x * x) % 4
+        }
+        fn double(x: u64) -> u64 {
+            (x * 2) % 4
+        }
+
+        let (cks, sks) = gen_keys(PARAM_MESSAGE_2_CARRY_2);
+        let functions: Vec<fn(u64) -> u64> = vec![square, double];
+
+        for msg in 0..4u64 {
+            let ct = cks.encrypt(msg);
+            let results = sks.multivalue_bootstrap(&ct, &functions);
+
+            assert_eq!(cks.decrypt(&results[0]), square(msg));
+            assert_eq!(cks.decrypt(&results[1]), double(msg));
+        }
+    }
+
+    #[test]
+    fn test_nary_bootstrap() {
+        let (cks, sks) = gen_keys(PARAM_MESSAGE_2_CARRY_2);
+
+        for a in 0..4u64 {
+            for b in 0..4u64 {
+                let ct_a = cks.encrypt(a);
+                let ct_b = cks.encrypt(b);
+
+                let ct_res = sks.nary_bootstrap(&[ct_a, ct_b], |digits| (digits[0] + digits[1] * 2) % 4);
+                let res = cks.decrypt(&ct_res);
+                assert_eq!(res, (a + b * 2) % 4);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "exceeds the available")]
+    fn test_nary_bootstrap_rejects_excess_arity() {
+        let (cks, sks) = gen_keys(PARAM_MESSAGE_2_CARRY_2);
+
+        // message_modulus=4, carry_modulus=4 gives headroom 16 = 4^2, so 3 packed inputs
+        // (4^3 = 64) must be rejected.
+        let inputs: Vec<_> = (0..3).map(|_| cks.encrypt(0)).collect();
+        sks.nary_bootstrap(&inputs, |digits| digits.iter().sum());
+    }
 }
@@ -0,0 +1,152 @@
+//! On-disk cache for generated [`WopbsKey`]s.
+//!
+//! Generating a [`WopbsKey`] via [`WopbsKey::new_wopbs_key`]/[`WopbsKey::new_wopbs_key_only_for_wopbs`]
+//! is very slow (it builds a pfpksk list and two server keys), and every test binary and benchmark
+//! would otherwise regenerate them from scratch. This is the WoPBS analogue of the shortint key
+//! cache used throughout the benchmark suites: keys are keyed on `(Parameters, wopbs Parameters)`,
+//! serialized to a configurable directory, and lazily loaded on subsequent [`WopbsKeyCache::get`]
+//! calls.
+
+use crate::shortint::gen_keys;
+use crate::shortint::parameters::Parameters;
+use crate::shortint::wopbs::WopbsKey;
+use lazy_static::lazy_static;
+use std::fs::{create_dir_all, File};
+use std::io::BufReader;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+lazy_static! {
+    pub static ref WOPBS_KEY_CACHE: WopbsKeyCache = WopbsKeyCache::new("wopbs_keys".into());
+}
+
+#[derive(Clone, Copy)]
+struct WopbsKeyCacheKey {
+    param: Parameters,
+    wopbs_param: Parameters,
+}
+
+impl PartialEq for WopbsKeyCacheKey {
+    fn eq(&self, other: &Self) -> bool {
+        // `Parameters` has no `PartialEq` impl of its own, so compare the full struct (every
+        // lwe/glwe dimension, base log, level, noise distribution, ...) via its serialized form
+        // rather than a hand-picked subset of fields, which could collide between genuinely
+        // different parameter sets.
+        hash_params(&self.param) == hash_params(&other.param)
+            && hash_params(&self.wopbs_param) == hash_params(&other.wopbs_param)
+    }
+}
+
+/// A keyed, persistent cache of generated [`WopbsKey`]s.
+pub struct WopbsKeyCache {
+    keys_dir: PathBuf,
+    in_memory: Mutex<Vec<(WopbsKeyCacheKey, WopbsKey)>>,
+}
+
+impl WopbsKeyCache {
+    pub fn new(keys_dir: PathBuf) -> Self {
+        Self {
+            keys_dir,
+            in_memory: Mutex::new(Vec::new()),
+        }
+    }
+
+    fn file_path(&self, key: &WopbsKeyCacheKey) -> PathBuf {
+        self.keys_dir.join(format!(
+            "wopbs_key_{:x}_{:x}.bin",
+            hash_params(&key.param),
+            hash_params(&key.wopbs_param)
+        ))
+    }
+
+    /// Returns the [`WopbsKey`] for the given `(param, wopbs_param)` pair, generating and
+    /// persisting it to disk on the first call, and loading it from disk (or from the in-memory
+    /// cache) on subsequent calls.
+    pub fn get(&self, param: Parameters, wopbs_param: Parameters) -> WopbsKey {
+        let cache_key = WopbsKeyCacheKey { param, wopbs_param };
+
+        if let Some((_, key)) = self
+            .in_memory
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(k, _)| *k == cache_key)
+        {
+            return key.clone();
+        }
+
+        let file_path = self.file_path(&cache_key);
+        let key = if let Ok(file) = File::open(&file_path) {
+            bincode::deserialize_from(BufReader::new(file))
+                .expect("failed to deserialize cached WopbsKey")
+        } else {
+            let (cks, sks) = gen_keys(param);
+            let key = WopbsKey::new_wopbs_key(&cks, &sks, &wopbs_param);
+
+            create_dir_all(&self.keys_dir).expect("failed to create WopbsKey cache directory");
+            let file = File::create(&file_path).expect("failed to create WopbsKey cache file");
+            bincode::serialize_into(file, &key).expect("failed to serialize WopbsKey");
+
+            key
+        };
+
+        self.in_memory
+            .lock()
+            .unwrap()
+            .push((cache_key, key.clone()));
+
+        key
+    }
+}
+
+/// Hashes the full `Parameters` struct (every lwe/glwe dimension, base log, level, noise
+/// distribution, ...), not just a hand-picked subset of fields, so that two distinct parameter
+/// sets can never collide into the same cache entry.
+fn hash_params(param: &Parameters) -> u64 {
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+
+    let serialized = bincode::serialize(param).expect("failed to serialize Parameters for hashing");
+    let mut hasher = DefaultHasher::new();
+    serialized.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortint::parameters::parameters_wopbs_message_carry::WOPBS_PARAM_MESSAGE_2_CARRY_2;
+    use crate::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+    #[test]
+    fn test_hash_params_deterministic_and_distinct() {
+        assert_eq!(
+            hash_params(&PARAM_MESSAGE_2_CARRY_2),
+            hash_params(&PARAM_MESSAGE_2_CARRY_2)
+        );
+        assert_ne!(
+            hash_params(&PARAM_MESSAGE_2_CARRY_2),
+            hash_params(&WOPBS_PARAM_MESSAGE_2_CARRY_2)
+        );
+    }
+
+    #[test]
+    fn test_wopbs_key_cache_hits_in_memory_on_second_get() {
+        let keys_dir = std::env::temp_dir().join(format!(
+            "tfhe_wopbs_key_cache_test_{:?}",
+            std::thread::current().id()
+        ));
+        let cache = WopbsKeyCache::new(keys_dir.clone());
+
+        let first = cache.get(PARAM_MESSAGE_2_CARRY_2, WOPBS_PARAM_MESSAGE_2_CARRY_2);
+        // The second call must be served from `in_memory`/disk rather than regenerating the key;
+        // serializing both to compare is the only equality this key type exposes.
+        let second = cache.get(PARAM_MESSAGE_2_CARRY_2, WOPBS_PARAM_MESSAGE_2_CARRY_2);
+
+        let first_bytes = bincode::serialize(&first).unwrap();
+        let second_bytes = bincode::serialize(&second).unwrap();
+        assert_eq!(first_bytes, second_bytes);
+
+        let _ = std::fs::remove_dir_all(&keys_dir);
+    }
+}